@@ -0,0 +1,148 @@
+//! Terminal rendering of album cover art: native graphics protocols (kitty,
+//! iTerm2, sixel) with a half-block Unicode fallback for terminals that
+//! support none of them.
+
+use base64::Engine;
+use image::{DynamicImage, GenericImageView};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalCapability {
+    Kitty,
+    Iterm,
+    Sixel,
+    Halfblock,
+}
+
+/// Sniff the terminal's image support from environment variables set by
+/// common terminal emulators. Falls back to the half-block renderer, which
+/// works everywhere ratatui does.
+pub fn detect_capability() -> TerminalCapability {
+    if std::env::var("KITTY_WINDOW_ID").is_ok() {
+        return TerminalCapability::Kitty;
+    }
+    if std::env::var("TERM_PROGRAM").is_ok_and(|v| v == "iTerm.app") {
+        return TerminalCapability::Iterm;
+    }
+    if std::env::var("TERM").is_ok_and(|v| v.contains("sixel") || v.contains("mlterm")) {
+        return TerminalCapability::Sixel;
+    }
+    TerminalCapability::Halfblock
+}
+
+/// Render `img` resized to fit `cols`x`rows` terminal cells as half-block
+/// Unicode ('▀'): the foreground color is the top sub-pixel and the
+/// background color the bottom, doubling vertical resolution per cell.
+pub fn render_halfblock(img: &DynamicImage, cols: u16, rows: u16) -> Vec<Line<'static>> {
+    if cols == 0 || rows == 0 {
+        return Vec::new();
+    }
+    let resized = img.resize_exact(
+        cols as u32,
+        rows as u32 * 2,
+        image::imageops::FilterType::Triangle,
+    );
+
+    let mut lines = Vec::with_capacity(rows as usize);
+    for row in 0..rows {
+        let mut spans = Vec::with_capacity(cols as usize);
+        for col in 0..cols {
+            let top = resized.get_pixel(col as u32, row as u32 * 2);
+            let bottom = resized.get_pixel(col as u32, row as u32 * 2 + 1);
+            let style = Style::default()
+                .fg(Color::Rgb(top[0], top[1], top[2]))
+                .bg(Color::Rgb(bottom[0], bottom[1], bottom[2]));
+            spans.push(Span::styled("▀", style));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Encode `png_bytes` as a kitty terminal graphics protocol APC escape that
+/// transmits and displays the image in one shot.
+pub fn encode_kitty(png_bytes: &[u8]) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png_bytes);
+    format!("\x1b_Ga=T,f=100,t=d;{}\x1b\\", encoded)
+}
+
+/// Encode `png_bytes` as an iTerm2 inline image escape sized to `cols`x`rows`
+/// terminal cells.
+pub fn encode_iterm(png_bytes: &[u8], cols: u16, rows: u16) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png_bytes);
+    format!(
+        "\x1b]1337;File=inline=1;width={};height={};preserveAspectRatio=1:{}\x07",
+        cols, rows, encoded
+    )
+}
+
+/// Encode `img` as a sixel image using a fixed 4x4x4-level RGB palette. This
+/// trades color fidelity for a simple, dependency-free encoder; fine for
+/// small cover-art thumbnails.
+pub fn encode_sixel(img: &DynamicImage, cols: u16, rows: u16) -> String {
+    const LEVELS: u32 = 4;
+    let width = (cols as u32 * 2).max(1);
+    let height = (rows as u32 * 2).max(1);
+    let resized = img
+        .resize_exact(width, height, image::imageops::FilterType::Triangle)
+        .to_rgb8();
+
+    let quantize = |v: u8| -> u32 { (v as u32 * (LEVELS - 1)) / 255 };
+    let palette_index =
+        |r: u8, g: u8, b: u8| -> u32 { quantize(r) * LEVELS * LEVELS + quantize(g) * LEVELS + quantize(b) };
+
+    let mut out = String::from("\x1bPq");
+    for pr in 0..LEVELS {
+        for pg in 0..LEVELS {
+            for pb in 0..LEVELS {
+                let idx = pr * LEVELS * LEVELS + pg * LEVELS + pb;
+                out.push_str(&format!(
+                    "#{};2;{};{};{}",
+                    idx,
+                    pr * 100 / (LEVELS - 1),
+                    pg * 100 / (LEVELS - 1),
+                    pb * 100 / (LEVELS - 1)
+                ));
+            }
+        }
+    }
+
+    for band_y in (0..height).step_by(6) {
+        for pr in 0..LEVELS {
+            for pg in 0..LEVELS {
+                for pb in 0..LEVELS {
+                    let idx = pr * LEVELS * LEVELS + pg * LEVELS + pb;
+                    let mut row = String::new();
+                    let mut any = false;
+                    for x in 0..width {
+                        let mut bits = 0u8;
+                        for dy in 0..6 {
+                            let y = band_y + dy;
+                            if y >= height {
+                                continue;
+                            }
+                            let pixel = resized.get_pixel(x, y);
+                            if palette_index(pixel[0], pixel[1], pixel[2]) == idx {
+                                bits |= 1 << dy;
+                                any = true;
+                            }
+                        }
+                        row.push((63 + bits) as u8 as char);
+                    }
+                    if any {
+                        out.push('#');
+                        out.push_str(&idx.to_string());
+                        out.push_str(&row);
+                        out.push('$');
+                    }
+                }
+            }
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
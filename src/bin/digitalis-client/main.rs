@@ -1,21 +1,35 @@
+mod cover_art;
+
 use clap::Parser;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyEventKind,
+        KeyModifiers, MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use digitalis::{Library, PlayRequest, PlaybackStatus, Track, VolumeRequest};
+use digitalis::{
+    ApiResponse, AudioEvent, Library, PlayRequest, PlaybackStatus, SeekRequest, Track,
+    VolumeRequest,
+};
+use futures_util::StreamExt;
 use ratatui::{
     Frame, Terminal,
     backend::{Backend, CrosstermBackend},
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Gauge, List, ListItem, Paragraph, Wrap},
 };
 use std::{
-    io, net::SocketAddr, time::{Duration, Instant}
+    collections::HashMap,
+    io::{self, Write},
+    net::SocketAddr,
+    time::{Duration, Instant},
 };
+use tokio::sync::mpsc;
+use tokio::time::interval;
 
 #[derive(Parser, Debug)]
 #[command(name = "digitalis-client")]
@@ -41,16 +55,49 @@ struct App {
 
     active_panel: Panel,
 
+    queue: Vec<Track>,
+    selected_queue: usize,
+
+    browser_columns: [u16; 3],
+
+    seek_bar_area: Rect,
+
+    minibuffer_active: bool,
+    filter_query: String,
+    filtered_indices: Vec<usize>,
+
+    show_lyrics: bool,
+    lyrics_synced: Vec<(u64, String)>,
+    lyrics_unsynced: Option<String>,
+    lyrics_page: usize,
+    active_lyric_idx: usize,
+    next_lyric_change_ms: u64,
+
+    terminal_capability: cover_art::TerminalCapability,
+    cover_cache: HashMap<String, image::DynamicImage>,
+    cover_area: Rect,
+    pending_image_escape: Option<String>,
+    /// Cached (album, width, height, encoded escape payload) for the native
+    /// graphics protocols, so `draw()` only re-encodes when the album or the
+    /// cover area's size actually changes instead of on every frame.
+    cover_escape_cache: Option<(String, u16, u16, String)>,
+
     loading: bool,
     error_message: Option<String>,
     last_update: Instant,
 }
 
+/// How far ahead of the real playback position we consider a lyric line "current",
+/// to compensate for render/refresh latency.
+const LYRICS_TIME_OFFSET_MS: u64 = 1000;
+const LYRICS_PAGE_CHARS: usize = 3000;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Panel {
     Artists,
     Albums,
     Tracks,
+    Queue,
 }
 
 impl App {
@@ -66,6 +113,24 @@ impl App {
             selected_album: 0,
             selected_track: 0,
             active_panel: Panel::Artists,
+            queue: Vec::new(),
+            selected_queue: 0,
+            browser_columns: [33, 33, 34],
+            seek_bar_area: Rect::default(),
+            minibuffer_active: false,
+            filter_query: String::new(),
+            filtered_indices: Vec::new(),
+            show_lyrics: false,
+            lyrics_synced: Vec::new(),
+            lyrics_unsynced: None,
+            lyrics_page: 0,
+            active_lyric_idx: 0,
+            next_lyric_change_ms: 0,
+            terminal_capability: cover_art::detect_capability(),
+            cover_cache: HashMap::new(),
+            cover_area: Rect::default(),
+            pending_image_escape: None,
+            cover_escape_cache: None,
             loading: true,
             error_message: None,
             last_update: Instant::now(),
@@ -74,7 +139,7 @@ impl App {
 
     async fn fetch_library(&mut self, client: &reqwest::Client) -> anyhow::Result<()> {
         let url = format!("{}/api/library", self.server);
-        let library = client.get(&url).send().await?.json::<Library>().await?;
+        let library = unwrap_response(client.get(&url).send().await?).await?;
         self.library = Some(library);
         self.fetch_artists(client).await?;
         Ok(())
@@ -82,7 +147,8 @@ impl App {
 
     async fn fetch_artists(&mut self, client: &reqwest::Client) -> anyhow::Result<()> {
         let url = format!("{}/api/library/artists", self.server);
-        self.artists = client.get(&url).send().await?.json::<Vec<String>>().await?;
+        self.artists = unwrap_response(client.get(&url).send().await?).await?;
+        self.recompute_filter();
         if !self.artists.is_empty() {
             self.fetch_albums(client).await?;
         }
@@ -99,7 +165,10 @@ impl App {
             self.server,
             urlencoding::encode(artist)
         );
-        self.albums = client.get(&url).send().await?.json::<Vec<String>>().await?;
+        self.albums = unwrap_response(client.get(&url).send().await?).await?;
+        if self.active_panel == Panel::Albums {
+            self.recompute_filter();
+        }
         if !self.albums.is_empty() {
             self.fetch_tracks(client).await?;
         }
@@ -118,21 +187,96 @@ impl App {
             urlencoding::encode(artist),
             urlencoding::encode(album)
         );
-        self.tracks = client.get(&url).send().await?.json::<Vec<Track>>().await?;
+        self.tracks = unwrap_response(client.get(&url).send().await?).await?;
+        if self.active_panel == Panel::Tracks {
+            self.recompute_filter();
+        }
         Ok(())
     }
 
-    async fn fetch_status(&mut self, client: &reqwest::Client) -> anyhow::Result<()> {
-        let url = format!("{}/api/status", self.server);
-        self.playback_status = client
+    async fn fetch_lyrics(&mut self, client: &reqwest::Client, track: &Track) -> anyhow::Result<()> {
+        let url = format!("{}/api/lyrics", self.server);
+        let response = client
             .get(&url)
+            .query(&[("path", track.path.as_str())])
             .send()
-            .await?
-            .json::<PlaybackStatus>()
             .await?;
+
+        if !response.status().is_success() {
+            self.lyrics_synced.clear();
+            self.lyrics_unsynced = None;
+            self.lyrics_page = 0;
+            return Ok(());
+        }
+
+        let body = response.text().await?;
+        let synced = parse_lrc(&body);
+        if synced.is_empty() {
+            self.lyrics_synced.clear();
+            self.lyrics_unsynced = Some(body);
+            self.lyrics_page = 0;
+        } else {
+            self.lyrics_synced = synced;
+            self.lyrics_unsynced = None;
+            self.active_lyric_idx = 0;
+            self.next_lyric_change_ms = self
+                .lyrics_synced
+                .get(1)
+                .map(|(ms, _)| *ms)
+                .unwrap_or(u64::MAX);
+        }
         Ok(())
     }
 
+    /// Fetch and decode the cover art for `track`, caching the decoded image by
+    /// album so switching tracks within the same album doesn't refetch.
+    async fn fetch_cover(&mut self, client: &reqwest::Client, track: &Track) -> anyhow::Result<()> {
+        if self.cover_cache.contains_key(&track.album) {
+            return Ok(());
+        }
+        let url = format!("{}/api/cover", self.server);
+        let response = client
+            .get(&url)
+            .query(&[("path", track.path.as_str())])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Ok(());
+        }
+
+        let bytes = response.bytes().await?;
+        if let Ok(image) = image::load_from_memory(&bytes) {
+            self.cover_cache.insert(track.album.clone(), image);
+        }
+        Ok(())
+    }
+
+    /// Recompute the active synced-lyric line, but only once playback has crossed
+    /// the next known timestamp, so we don't rescan the whole list every tick.
+    fn update_active_lyric(&mut self) {
+        if self.lyrics_synced.is_empty() {
+            return;
+        }
+        let position = self.playback_status.position_ms + LYRICS_TIME_OFFSET_MS;
+        if position < self.next_lyric_change_ms {
+            return;
+        }
+        self.active_lyric_idx = match self
+            .lyrics_synced
+            .binary_search_by_key(&position, |(ms, _)| *ms)
+        {
+            Ok(idx) => idx,
+            Err(0) => 0,
+            Err(idx) => idx - 1,
+        };
+        self.next_lyric_change_ms = self
+            .lyrics_synced
+            .get(self.active_lyric_idx + 1)
+            .map(|(ms, _)| *ms)
+            .unwrap_or(u64::MAX);
+    }
+
     async fn play_track(&self, client: &reqwest::Client, track: &Track) -> anyhow::Result<()> {
         let url = format!("{}/api/play", self.server);
         let request = PlayRequest {
@@ -169,12 +313,40 @@ impl App {
         Ok(())
     }
 
+    async fn seek(&self, client: &reqwest::Client, position_ms: u64) -> anyhow::Result<()> {
+        let url = format!("{}/api/seek", self.server);
+        let request = SeekRequest { position_ms };
+        client.post(&url).json(&request).send().await?;
+        Ok(())
+    }
+
+    /// Translate a clicked column/row within the seek bar's `Rect` into a
+    /// target playback offset, or `None` if the click landed outside the bar
+    /// or there is no known track duration to scrub within.
+    fn seek_target_ms(&self, column: u16, row: u16) -> Option<u64> {
+        let duration = self.playback_status.duration_ms?;
+        let area = self.seek_bar_area;
+        if area.width == 0
+            || column < area.x
+            || column >= area.x + area.width
+            || row < area.y
+            || row >= area.y + area.height
+        {
+            return None;
+        }
+        let offset = (column - area.x) as f64 / area.width as f64;
+        Some((offset.clamp(0.0, 1.0) * duration as f64) as u64)
+    }
+
     fn next_panel(&mut self) {
         self.active_panel = match self.active_panel {
             Panel::Artists => Panel::Albums,
             Panel::Albums => Panel::Tracks,
-            Panel::Tracks => Panel::Tracks,
+            Panel::Tracks => Panel::Queue,
+            Panel::Queue => Panel::Queue,
         };
+        self.filter_query.clear();
+        self.recompute_filter();
     }
 
     fn prev_panel(&mut self) {
@@ -182,51 +354,164 @@ impl App {
             Panel::Artists => Panel::Artists,
             Panel::Albums => Panel::Artists,
             Panel::Tracks => Panel::Albums,
+            Panel::Queue => Panel::Tracks,
         };
+        self.filter_query.clear();
+        self.recompute_filter();
     }
 
-    fn next_item(&mut self) {
+    /// Refresh the local queue mirror from the server's authoritative queue.
+    async fn refresh_queue(&mut self, client: &reqwest::Client) -> anyhow::Result<()> {
+        let url = format!("{}/api/queue", self.server);
+        self.queue = unwrap_response(client.get(&url).send().await?).await?;
+        if self.active_panel == Panel::Queue {
+            self.recompute_filter();
+        }
+        Ok(())
+    }
+
+    async fn enqueue_track(&mut self, client: &reqwest::Client, track: &Track) -> anyhow::Result<()> {
+        let url = format!("{}/api/queue", self.server);
+        let request = PlayRequest {
+            path: track.path.clone(),
+        };
+        client.post(&url).json(&request).send().await?;
+        self.refresh_queue(client).await
+    }
+
+    async fn enqueue_album(&mut self, client: &reqwest::Client) -> anyhow::Result<()> {
+        for track in self.tracks.clone() {
+            let url = format!("{}/api/queue", self.server);
+            let request = PlayRequest { path: track.path };
+            client.post(&url).json(&request).send().await?;
+        }
+        self.refresh_queue(client).await
+    }
+
+    async fn clear_queue(&mut self, client: &reqwest::Client) -> anyhow::Result<()> {
+        let url = format!("{}/api/queue", self.server);
+        client.delete(&url).send().await?;
+        self.queue.clear();
+        self.selected_queue = 0;
+        self.recompute_filter();
+        Ok(())
+    }
+
+    async fn remove_queue_entry(
+        &mut self,
+        client: &reqwest::Client,
+        index: usize,
+    ) -> anyhow::Result<()> {
+        let url = format!("{}/api/queue/{}", self.server, index);
+        client.delete(&url).send().await?;
+        self.refresh_queue(client).await?;
+        if self.selected_queue >= self.queue.len() {
+            self.selected_queue = self.queue.len().saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    async fn next_track(&self, client: &reqwest::Client) -> anyhow::Result<()> {
+        let url = format!("{}/api/next", self.server);
+        client.post(&url).send().await?;
+        Ok(())
+    }
+
+    async fn previous_track(&self, client: &reqwest::Client) -> anyhow::Result<()> {
+        let url = format!("{}/api/previous", self.server);
+        client.post(&url).send().await?;
+        Ok(())
+    }
+
+    fn widen_column(&mut self, index: usize) {
+        let next = (index + 1) % self.browser_columns.len();
+        if self.browser_columns[next] > 5 {
+            self.browser_columns[index] += 1;
+            self.browser_columns[next] -= 1;
+        }
+    }
+
+    fn narrow_column(&mut self, index: usize) {
+        let next = (index + 1) % self.browser_columns.len();
+        if self.browser_columns[index] > 5 {
+            self.browser_columns[index] -= 1;
+            self.browser_columns[next] += 1;
+        }
+    }
+
+    /// Display label for each entry of the currently active browser panel, in the
+    /// order the raw `artists`/`albums`/`tracks` vectors store them.
+    fn panel_labels(&self) -> Vec<&str> {
         match self.active_panel {
-            Panel::Artists => {
-                if !self.artists.is_empty() {
-                    self.selected_artist = (self.selected_artist + 1) % self.artists.len();
-                }
-            }
-            Panel::Albums => {
-                if !self.albums.is_empty() {
-                    self.selected_album = (self.selected_album + 1) % self.albums.len();
-                }
-            }
-            Panel::Tracks => {
-                if !self.tracks.is_empty() {
-                    self.selected_track = (self.selected_track + 1) % self.tracks.len();
-                }
-            }
+            Panel::Artists => self.artists.iter().map(String::as_str).collect(),
+            Panel::Albums => self.albums.iter().map(String::as_str).collect(),
+            Panel::Tracks => self.tracks.iter().map(|t| t.title.as_str()).collect(),
+            Panel::Queue => self.queue.iter().map(|t| t.title.as_str()).collect(),
+        }
+    }
+
+    /// Recompute `filtered_indices` for the active panel from `filter_query`,
+    /// scoring candidates by fuzzy subsequence match and sorting best-first.
+    /// An empty query yields the identity ordering (every entry, unfiltered).
+    fn recompute_filter(&mut self) {
+        let labels = self.panel_labels();
+        if self.filter_query.is_empty() {
+            self.filtered_indices = (0..labels.len()).collect();
+            return;
+        }
+        let mut scored: Vec<(usize, i32)> = labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, label)| fuzzy_score(&self.filter_query, label).map(|score| (i, score)))
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        self.filtered_indices = scored.into_iter().map(|(i, _)| i).collect();
+    }
+
+    fn next_item(&mut self) {
+        let selected = match self.active_panel {
+            Panel::Artists => &mut self.selected_artist,
+            Panel::Albums => &mut self.selected_album,
+            Panel::Tracks => &mut self.selected_track,
+            Panel::Queue => &mut self.selected_queue,
+        };
+        if self.filtered_indices.is_empty() {
+            return;
         }
+        let pos = self
+            .filtered_indices
+            .iter()
+            .position(|&i| i == *selected)
+            .unwrap_or(0);
+        let next_pos = (pos + 1) % self.filtered_indices.len();
+        *selected = self.filtered_indices[next_pos];
     }
 
     fn prev_item(&mut self) {
-        match self.active_panel {
-            Panel::Artists => {
-                if !self.artists.is_empty() {
-                    self.selected_artist = self.selected_artist.saturating_sub(1);
-                }
-            }
-            Panel::Albums => {
-                if !self.albums.is_empty() {
-                    self.selected_album = self.selected_album.saturating_sub(1);
-                }
-            }
-            Panel::Tracks => {
-                if !self.tracks.is_empty() {
-                    self.selected_track = self.selected_track.saturating_sub(1);
-                }
-            }
+        let selected = match self.active_panel {
+            Panel::Artists => &mut self.selected_artist,
+            Panel::Albums => &mut self.selected_album,
+            Panel::Tracks => &mut self.selected_track,
+            Panel::Queue => &mut self.selected_queue,
+        };
+        if self.filtered_indices.is_empty() {
+            return;
         }
+        let pos = self
+            .filtered_indices
+            .iter()
+            .position(|&i| i == *selected)
+            .unwrap_or(0);
+        let prev_pos = if pos == 0 {
+            self.filtered_indices.len() - 1
+        } else {
+            pos - 1
+        };
+        *selected = self.filtered_indices[prev_pos];
     }
 }
 
-fn draw(f: &mut Frame, app: &App) {
+fn draw(f: &mut Frame, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
@@ -234,6 +519,7 @@ fn draw(f: &mut Frame, app: &App) {
             Constraint::Length(3),
             Constraint::Min(10),
             Constraint::Length(8),
+            Constraint::Length(8),
         ])
         .split(f.area());
 
@@ -252,18 +538,17 @@ fn draw(f: &mut Frame, app: &App) {
     let browser_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
+            Constraint::Percentage(app.browser_columns[0]),
+            Constraint::Percentage(app.browser_columns[1]),
+            Constraint::Percentage(app.browser_columns[2]),
         ])
         .split(chunks[1]);
 
     // Artists list
-    let artists_items: Vec<ListItem> = app
-        .artists
-        .iter()
-        .enumerate()
-        .map(|(i, artist)| {
+    let artists_items: Vec<ListItem> = visible_indices(app, Panel::Artists, app.artists.len())
+        .into_iter()
+        .map(|i| {
+            let artist = &app.artists[i];
             let style = if i == app.selected_artist {
                 if app.active_panel == Panel::Artists {
                     Style::default().bg(Color::Blue).fg(Color::White)
@@ -292,11 +577,10 @@ fn draw(f: &mut Frame, app: &App) {
     f.render_widget(artists_list, browser_chunks[0]);
 
     // Albums list
-    let albums_items: Vec<ListItem> = app
-        .albums
-        .iter()
-        .enumerate()
-        .map(|(i, album)| {
+    let albums_items: Vec<ListItem> = visible_indices(app, Panel::Albums, app.albums.len())
+        .into_iter()
+        .map(|i| {
+            let album = &app.albums[i];
             let style = if i == app.selected_album {
                 if app.active_panel == Panel::Albums {
                     Style::default().bg(Color::Blue).fg(Color::White)
@@ -325,11 +609,10 @@ fn draw(f: &mut Frame, app: &App) {
     f.render_widget(albums_list, browser_chunks[1]);
 
     // Tracks list
-    let tracks_items: Vec<ListItem> = app
-        .tracks
-        .iter()
-        .enumerate()
-        .map(|(i, track)| {
+    let tracks_items: Vec<ListItem> = visible_indices(app, Panel::Tracks, app.tracks.len())
+        .into_iter()
+        .map(|i| {
+            let track = &app.tracks[i];
             let style = if i == app.selected_track {
                 if app.active_panel == Panel::Tracks {
                     Style::default().bg(Color::Blue).fg(Color::White)
@@ -357,11 +640,53 @@ fn draw(f: &mut Frame, app: &App) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
     f.render_widget(tracks_list, browser_chunks[2]);
 
+    // Queue panel
+    let queue_items: Vec<ListItem> = app
+        .queue
+        .iter()
+        .enumerate()
+        .map(|(i, track)| {
+            let style = if i == app.selected_queue {
+                if app.active_panel == Panel::Queue {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default().bg(Color::DarkGray).fg(Color::White)
+                }
+            } else {
+                Style::default()
+            };
+            let marker = if app.playback_status.track.as_ref().is_some_and(|t| t.path == track.path) {
+                "▶ "
+            } else {
+                "  "
+            };
+            ListItem::new(format!("{}{}", marker, track.title)).style(style)
+        })
+        .collect();
+
+    let queue_list = List::new(queue_items)
+        .block(
+            Block::default()
+                .title("Queue")
+                .borders(Borders::ALL)
+                .border_style(if app.active_panel == Panel::Queue {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                }),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+    f.render_widget(queue_list, chunks[2]);
+
     // Now playing area
     let now_playing_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(chunks[2]);
+        .constraints([
+            Constraint::Percentage(45),
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
+        ])
+        .split(chunks[3]);
 
     let mut now_playing_text = vec![];
 
@@ -378,10 +703,33 @@ fn draw(f: &mut Frame, app: &App) {
         now_playing_text.push(Line::from("Nothing playing"));
     }
 
-    now_playing_text.push(Line::from(""));
+    let now_playing_block = Block::default().title("Now Playing").borders(Borders::ALL);
+    let now_playing_inner = now_playing_block.inner(now_playing_chunks[0]);
+    f.render_widget(now_playing_block, now_playing_chunks[0]);
+
+    let now_playing_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(now_playing_text.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(now_playing_inner);
+
+    let info = Paragraph::new(now_playing_text).wrap(Wrap { trim: true });
+    f.render_widget(info, now_playing_rows[0]);
+
+    app.seek_bar_area = now_playing_rows[1];
 
     let position_secs = app.playback_status.position_ms / 1000;
-    let position_str = format!("{:02}:{:02}", position_secs / 60, position_secs % 60);
+    let duration_secs = app.playback_status.duration_ms.unwrap_or(0) / 1000;
+    let position_str = format!(
+        "{:02}:{:02} / {:02}:{:02}",
+        position_secs / 60,
+        position_secs % 60,
+        duration_secs / 60,
+        duration_secs % 60
+    );
 
     let status_icon = if app.playback_status.playing {
         "▶"
@@ -389,16 +737,23 @@ fn draw(f: &mut Frame, app: &App) {
         "⏸"
     };
 
-    now_playing_text.push(Line::from(format!("{} {}", status_icon, position_str)));
+    let ratio = app
+        .playback_status
+        .duration_ms
+        .filter(|d| *d > 0)
+        .map(|d| (app.playback_status.position_ms as f64 / d as f64).clamp(0.0, 1.0))
+        .unwrap_or(0.0);
+
+    let seek_gauge = Gauge::default()
+        .gauge_style(Style::default().fg(Color::Cyan))
+        .label(format!("{} {}", status_icon, position_str))
+        .ratio(ratio);
+    f.render_widget(seek_gauge, now_playing_rows[1]);
 
     let volume_bar = "█".repeat((app.playback_status.volume * 10.0) as usize)
         + &"░".repeat(10 - (app.playback_status.volume * 10.0) as usize);
-    now_playing_text.push(Line::from(format!("Volume: [{}]", volume_bar)));
-
-    let now_playing = Paragraph::new(now_playing_text)
-        .block(Block::default().title("Now Playing").borders(Borders::ALL))
-        .wrap(Wrap { trim: true });
-    f.render_widget(now_playing, now_playing_chunks[0]);
+    let volume_line = Paragraph::new(format!("Volume: [{}]", volume_bar));
+    f.render_widget(volume_line, now_playing_rows[2]);
 
     // Controls
     let controls_text = Text::from(vec![
@@ -426,11 +781,167 @@ fn draw(f: &mut Frame, app: &App) {
             Span::styled("Q", Style::default().fg(Color::Green)),
             Span::raw(" Quit"),
         ]),
+        Line::from(vec![
+            Span::styled("a/A", Style::default().fg(Color::Green)),
+            Span::raw(" Enqueue track/album"),
+        ]),
+        Line::from(vec![
+            Span::styled("c", Style::default().fg(Color::Green)),
+            Span::raw(" Clear queue  "),
+            Span::styled("d", Style::default().fg(Color::Green)),
+            Span::raw(" Remove queue entry"),
+        ]),
+        Line::from(vec![
+            Span::styled("n/p", Style::default().fg(Color::Green)),
+            Span::raw(" Next/Previous"),
+        ]),
+        Line::from(vec![
+            Span::styled("Shift+← →", Style::default().fg(Color::Green)),
+            Span::raw(" Resize columns"),
+        ]),
+        Line::from(vec![
+            Span::styled("L", Style::default().fg(Color::Green)),
+            Span::raw(" Toggle lyrics"),
+        ]),
+        Line::from(vec![
+            Span::styled("/", Style::default().fg(Color::Green)),
+            Span::raw(" Fuzzy search"),
+        ]),
+        Line::from(vec![
+            Span::styled("[ / ]", Style::default().fg(Color::Green)),
+            Span::raw(" Seek -5s/+5s, click/drag bar"),
+        ]),
     ]);
 
     let controls = Paragraph::new(controls_text)
         .block(Block::default().title("Controls").borders(Borders::ALL));
-    f.render_widget(controls, now_playing_chunks[1]);
+    f.render_widget(controls, now_playing_chunks[2]);
+
+    // Cover art
+    let cover_block = Block::default().title("Cover").borders(Borders::ALL);
+    let cover_inner = cover_block.inner(now_playing_chunks[1]);
+    f.render_widget(cover_block, now_playing_chunks[1]);
+    app.cover_area = cover_inner;
+    app.pending_image_escape = None;
+
+    if let Some(ref track) = app.playback_status.track {
+        if let Some(image) = app.cover_cache.get(&track.album) {
+            match app.terminal_capability {
+                cover_art::TerminalCapability::Halfblock => {
+                    let lines = cover_art::render_halfblock(image, cover_inner.width, cover_inner.height);
+                    f.render_widget(Paragraph::new(lines), cover_inner);
+                }
+                capability => {
+                    let cache_hit = app
+                        .cover_escape_cache
+                        .as_ref()
+                        .is_some_and(|(album, w, h, _)| {
+                            album == &track.album
+                                && *w == cover_inner.width
+                                && *h == cover_inner.height
+                        });
+                    if !cache_hit {
+                        let mut png_bytes = Vec::new();
+                        if image
+                            .write_to(&mut io::Cursor::new(&mut png_bytes), image::ImageFormat::Png)
+                            .is_ok()
+                        {
+                            let payload = match capability {
+                                cover_art::TerminalCapability::Kitty => cover_art::encode_kitty(&png_bytes),
+                                cover_art::TerminalCapability::Iterm => cover_art::encode_iterm(
+                                    &png_bytes,
+                                    cover_inner.width,
+                                    cover_inner.height,
+                                ),
+                                cover_art::TerminalCapability::Sixel => {
+                                    cover_art::encode_sixel(image, cover_inner.width, cover_inner.height)
+                                }
+                                cover_art::TerminalCapability::Halfblock => unreachable!(),
+                            };
+                            app.cover_escape_cache = Some((
+                                track.album.clone(),
+                                cover_inner.width,
+                                cover_inner.height,
+                                payload,
+                            ));
+                        }
+                    }
+                    if let Some((_, _, _, payload)) = &app.cover_escape_cache {
+                        app.pending_image_escape = Some(format!(
+                            "\x1b[{};{}H{}",
+                            cover_inner.y + 1,
+                            cover_inner.x + 1,
+                            payload
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    // Lyrics overlay
+    if app.show_lyrics {
+        let area = centered_rect(70, 70, f.area());
+        f.render_widget(Clear, area);
+
+        let lyrics_widget = if !app.lyrics_synced.is_empty() {
+            let mut lines = Vec::new();
+            let idx = app.active_lyric_idx;
+            let start = idx.saturating_sub(3);
+            let end = (idx + 4).min(app.lyrics_synced.len());
+            for (i, (_, text)) in app.lyrics_synced[start..end].iter().enumerate() {
+                let actual_idx = start + i;
+                let style = if actual_idx == idx {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                lines.push(Line::from(Span::styled(text.as_str(), style)).alignment(Alignment::Center));
+            }
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title("Lyrics")
+                    .borders(Borders::ALL),
+            )
+        } else if let Some(ref text) = app.lyrics_unsynced {
+            let page_start = app.lyrics_page * LYRICS_PAGE_CHARS;
+            let page = text
+                .chars()
+                .skip(page_start)
+                .take(LYRICS_PAGE_CHARS)
+                .collect::<String>();
+            Paragraph::new(page)
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .title(format!("Lyrics (PageUp/PageDown, page {})", app.lyrics_page + 1))
+                        .borders(Borders::ALL),
+                )
+        } else {
+            Paragraph::new("No lyrics available").block(
+                Block::default()
+                    .title("Lyrics")
+                    .borders(Borders::ALL),
+            )
+        };
+        f.render_widget(lyrics_widget, area);
+    }
+
+    // Fuzzy-search minibuffer
+    if app.minibuffer_active {
+        let area = Rect {
+            x: f.area().x,
+            y: f.area().bottom().saturating_sub(1),
+            width: f.area().width,
+            height: 1,
+        };
+        f.render_widget(Clear, area);
+        let minibuffer = Paragraph::new(format!("/{}", app.filter_query))
+            .style(Style::default().fg(Color::White).bg(Color::DarkGray));
+        f.render_widget(minibuffer, area);
+    }
 
     // Error message overlay
     if let Some(ref error) = app.error_message {
@@ -454,6 +965,106 @@ fn draw(f: &mut Frame, app: &App) {
     }
 }
 
+/// Indices to render for a browser list: the active panel's `filtered_indices`
+/// when a search is narrowing it, or every index otherwise.
+fn visible_indices(app: &App, panel: Panel, len: usize) -> Vec<usize> {
+    if app.active_panel == panel && !app.filter_query.is_empty() {
+        app.filtered_indices.clone()
+    } else {
+        (0..len).collect()
+    }
+}
+
+/// Score a fuzzy subsequence match of `query` against `candidate`, case-insensitively.
+/// Returns `None` if `query`'s characters don't all appear in order. Consecutive
+/// matches and matches right after a separator/word boundary score higher; gaps
+/// between matches and an unmatched leading prefix are penalized.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let chars: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score = 0i32;
+    let mut qi = 0;
+    let mut last_match: Option<usize> = None;
+    let mut first_match: Option<usize> = None;
+
+    for (ci, &c) in chars.iter().enumerate() {
+        if qi >= query.len() {
+            break;
+        }
+        if c == query[qi] {
+            if first_match.is_none() {
+                first_match = Some(ci);
+            }
+            let boundary = ci == 0
+                || matches!(chars[ci - 1], ' ' | '-' | '_' | '/' | '.');
+            if boundary {
+                score += 10;
+            }
+            match last_match {
+                Some(prev) if prev + 1 == ci => score += 15,
+                Some(prev) => score -= (ci - prev) as i32,
+                None => {}
+            }
+            last_match = Some(ci);
+            qi += 1;
+        }
+    }
+
+    if qi < query.len() {
+        return None;
+    }
+
+    score -= first_match.unwrap_or(0) as i32;
+    Some(score)
+}
+
+/// Deserialize an HTTP response body as the `ApiResponse` envelope the server
+/// wraps every JSON reply in, and unwrap it into the inner value or an error.
+async fn unwrap_response<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> anyhow::Result<T> {
+    match response.json::<ApiResponse<T>>().await? {
+        ApiResponse::Success(value) => Ok(value),
+        ApiResponse::Failure(msg) => Err(anyhow::anyhow!(msg)),
+        ApiResponse::Fatal(msg) => Err(anyhow::anyhow!(msg)),
+    }
+}
+
+/// Parse LRC-style lyrics (`[mm:ss.xx] text` per line) into a timestamp-sorted list.
+/// Lines without a recognizable `[mm:ss.xx]` tag are ignored; if none are found
+/// the caller should fall back to treating the input as plain unsynced text.
+fn parse_lrc(input: &str) -> Vec<(u64, String)> {
+    let mut lines = Vec::new();
+    for line in input.lines() {
+        let line = line.trim();
+        if !line.starts_with('[') {
+            continue;
+        }
+        let Some(end) = line.find(']') else {
+            continue;
+        };
+        let tag = &line[1..end];
+        let Some((minutes, rest)) = tag.split_once(':') else {
+            continue;
+        };
+        let Ok(minutes) = minutes.parse::<u64>() else {
+            continue;
+        };
+        let Ok(seconds) = rest.parse::<f64>() else {
+            continue;
+        };
+        let ms = minutes * 60_000 + (seconds * 1000.0) as u64;
+        let text = line[end + 1..].trim().to_string();
+        lines.push((ms, text));
+    }
+    lines.sort_by_key(|(ms, _)| *ms);
+    lines
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -474,6 +1085,78 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// An update pushed from the `/api/events` subscription task into the main loop.
+enum StatusUpdate {
+    Event(AudioEvent),
+    Disconnected(String),
+}
+
+/// Subscribe to the server's SSE status stream and forward decoded frames over
+/// an `mpsc` channel, reconnecting with exponential backoff on disconnect.
+fn spawn_status_stream(server: SocketAddr, client: reqwest::Client) -> mpsc::Receiver<StatusUpdate> {
+    let (tx, rx) = mpsc::channel(32);
+
+    tokio::spawn(async move {
+        let initial_backoff = Duration::from_millis(500);
+        let max_backoff = Duration::from_secs(10);
+        let mut backoff = initial_backoff;
+
+        loop {
+            match subscribe_once(server, &client, &tx).await {
+                Ok(received_any) => {
+                    if received_any {
+                        backoff = initial_backoff;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(StatusUpdate::Disconnected(e.to_string())).await;
+                }
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(max_backoff);
+        }
+    });
+
+    rx
+}
+
+/// Connect once to `GET /api/events` and forward `data:` frames until the
+/// connection drops. Returns whether at least one frame was received.
+async fn subscribe_once(
+    server: SocketAddr,
+    client: &reqwest::Client,
+    tx: &mpsc::Sender<StatusUpdate>,
+) -> anyhow::Result<bool> {
+    let url = format!("http://{}/api/events", server);
+    let response = client.get(&url).send().await?;
+    let mut stream = response.bytes_stream();
+    let mut buf = String::new();
+    let mut received_any = false;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        buf.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(pos) = buf.find("\n\n") {
+            let frame = buf[..pos].to_string();
+            buf.drain(..pos + 2);
+
+            for line in frame.lines() {
+                if let Some(data) = line.strip_prefix("data:") {
+                    if let Ok(event) = serde_json::from_str::<AudioEvent>(data.trim()) {
+                        received_any = true;
+                        if tx.send(StatusUpdate::Event(event)).await.is_err() {
+                            return Ok(received_any);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(received_any)
+}
+
 async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
     let client = reqwest::Client::new();
 
@@ -481,27 +1164,128 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Re
     if let Err(e) = app.fetch_library(&client).await {
         app.error_message = Some(format!("Failed to load library: {}", e));
     }
+    if let Err(e) = app.refresh_queue(&client).await {
+        app.error_message = Some(format!("Failed to load queue: {}", e));
+    }
     app.loading = false;
 
-    let mut last_tick = Instant::now();
     let tick_rate = Duration::from_millis(250);
-    let status_update_rate = Duration::from_secs(1);
-    let mut last_status_update = Instant::now();
+    let mut ticker = interval(tick_rate);
+    let mut events = EventStream::new();
+    let mut status_rx = spawn_status_stream(app.server, client.clone());
 
     loop {
-        terminal.draw(|f| draw(f, &app))?;
+        terminal.draw(|f| draw(f, &mut app))?;
 
-        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+        // ratatui has no native widget for raw graphics-protocol escapes, so
+        // kitty/iTerm/sixel cover art is flushed straight to stdout after the
+        // buffered frame is drawn, positioned via cursor-move escapes.
+        if let Some(escape) = app.pending_image_escape.take() {
+            let mut stdout = io::stdout();
+            let _ = stdout.write_all(escape.as_bytes());
+            let _ = stdout.flush();
+        }
 
-        if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
+        tokio::select! {
+            maybe_event = events.next() => {
+            match maybe_event {
+                None => return Ok(()),
+                Some(Err(e)) => {
+                    app.error_message = Some(format!("Input error: {}", e));
+                }
+                Some(Ok(Event::Key(key))) => {
                 if key.kind == KeyEventKind::Press {
+                    if app.minibuffer_active {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.minibuffer_active = false;
+                                app.filter_query.clear();
+                                app.recompute_filter();
+                            }
+                            KeyCode::Enter => {
+                                app.minibuffer_active = false;
+                            }
+                            KeyCode::Down => app.next_item(),
+                            KeyCode::Up => app.prev_item(),
+                            KeyCode::Backspace => {
+                                app.filter_query.pop();
+                                app.recompute_filter();
+                            }
+                            KeyCode::Char(c) => {
+                                app.filter_query.push(c);
+                                app.recompute_filter();
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+                    if key.modifiers.contains(KeyModifiers::SHIFT) {
+                        match key.code {
+                            KeyCode::Left => {
+                                let col = app.active_panel as usize;
+                                if col < app.browser_columns.len() {
+                                    app.narrow_column(col);
+                                }
+                                continue;
+                            }
+                            KeyCode::Right => {
+                                let col = app.active_panel as usize;
+                                if col < app.browser_columns.len() {
+                                    app.widen_column(col);
+                                }
+                                continue;
+                            }
+                            _ => {}
+                        }
+                    }
                     match key.code {
                         KeyCode::Char('q') | KeyCode::Char('Q') => return Ok(()),
                         KeyCode::Tab | KeyCode::Right => app.next_panel(),
                         KeyCode::Left => app.prev_panel(),
                         KeyCode::Down => app.next_item(),
                         KeyCode::Up => app.prev_item(),
+                        KeyCode::Char('a') => {
+                            if app.active_panel == Panel::Tracks {
+                                if let Some(track) = app.tracks.get(app.selected_track).cloned() {
+                                    if let Err(e) = app.enqueue_track(&client, &track).await {
+                                        app.error_message = Some(format!("Error: {}", e));
+                                    }
+                                }
+                            }
+                        }
+                        KeyCode::Char('A') => {
+                            if app.active_panel == Panel::Tracks {
+                                if let Err(e) = app.enqueue_album(&client).await {
+                                    app.error_message = Some(format!("Error: {}", e));
+                                }
+                            }
+                        }
+                        KeyCode::Char('c') => {
+                            if app.active_panel == Panel::Queue {
+                                if let Err(e) = app.clear_queue(&client).await {
+                                    app.error_message = Some(format!("Error: {}", e));
+                                }
+                            }
+                        }
+                        KeyCode::Char('d') => {
+                            if app.active_panel == Panel::Queue {
+                                if let Err(e) =
+                                    app.remove_queue_entry(&client, app.selected_queue).await
+                                {
+                                    app.error_message = Some(format!("Error: {}", e));
+                                }
+                            }
+                        }
+                        KeyCode::Char('n') => {
+                            if let Err(e) = app.next_track(&client).await {
+                                app.error_message = Some(format!("Error: {}", e));
+                            }
+                        }
+                        KeyCode::Char('p') => {
+                            if let Err(e) = app.previous_track(&client).await {
+                                app.error_message = Some(format!("Error: {}", e));
+                            }
+                        }
                         KeyCode::Enter => {
                             if app.active_panel == Panel::Artists {
                                 if let Err(e) = app.fetch_albums(&client).await {
@@ -512,8 +1296,14 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Re
                                     app.error_message = Some(format!("Error: {}", e));
                                 }
                             } else if app.active_panel == Panel::Tracks {
-                                if let Some(track) = app.tracks.get(app.selected_track) {
-                                    if let Err(e) = app.play_track(&client, track).await {
+                                if let Some(track) = app.tracks.get(app.selected_track).cloned() {
+                                    if let Err(e) = app.play_track(&client, &track).await {
+                                        app.error_message = Some(format!("Error: {}", e));
+                                    }
+                                    if let Err(e) = app.fetch_lyrics(&client, &track).await {
+                                        app.error_message = Some(format!("Error: {}", e));
+                                    }
+                                    if let Err(e) = app.fetch_cover(&client, &track).await {
                                         app.error_message = Some(format!("Error: {}", e));
                                     }
                                 }
@@ -535,6 +1325,36 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Re
                                 app.error_message = Some(format!("Error: {}", e));
                             }
                         }
+                        KeyCode::Char('l') | KeyCode::Char('L') => {
+                            app.show_lyrics = !app.show_lyrics;
+                        }
+                        KeyCode::Char('/') => {
+                            app.minibuffer_active = true;
+                        }
+                        KeyCode::Char('[') => {
+                            let target = app.playback_status.position_ms.saturating_sub(5000);
+                            if let Err(e) = app.seek(&client, target).await {
+                                app.error_message = Some(format!("Error: {}", e));
+                            }
+                        }
+                        KeyCode::Char(']') => {
+                            let target = (app.playback_status.position_ms + 5000)
+                                .min(app.playback_status.duration_ms.unwrap_or(u64::MAX));
+                            if let Err(e) = app.seek(&client, target).await {
+                                app.error_message = Some(format!("Error: {}", e));
+                            }
+                        }
+                        KeyCode::PageUp if app.show_lyrics => {
+                            app.lyrics_page = app.lyrics_page.saturating_sub(1);
+                        }
+                        KeyCode::PageDown if app.show_lyrics => {
+                            if let Some(ref text) = app.lyrics_unsynced {
+                                let max_page = text.chars().count() / LYRICS_PAGE_CHARS;
+                                if app.lyrics_page < max_page {
+                                    app.lyrics_page += 1;
+                                }
+                            }
+                        }
                         KeyCode::Char('+') | KeyCode::Char('=') => {
                             let new_volume = (app.playback_status.volume + 0.1).min(1.0);
                             if let Err(e) = app.set_volume(&client, new_volume).await {
@@ -550,21 +1370,61 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Re
                         _ => {}
                     }
                 }
+                }
+                Some(Ok(Event::Mouse(mouse))) => {
+                    let is_drag_or_click = matches!(
+                        mouse.kind,
+                        MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left)
+                    );
+                    if is_drag_or_click {
+                        if let Some(target_ms) = app.seek_target_ms(mouse.column, mouse.row) {
+                            if let Err(e) = app.seek(&client, target_ms).await {
+                                app.error_message = Some(format!("Error: {}", e));
+                            }
+                        }
+                    }
+                }
+                Some(Ok(_)) => {}
             }
-        }
-
-        if last_tick.elapsed() >= tick_rate {
-            last_tick = Instant::now();
-        }
-
-        // Update status periodically
-        if last_status_update.elapsed() >= status_update_rate {
-            if let Err(e) = app.fetch_status(&client).await {
-                app.error_message = Some(format!("Connection error: {}", e));
-            } else {
-                app.error_message = None;
             }
-            last_status_update = Instant::now();
+            status = status_rx.recv() => {
+                match status {
+                    Some(StatusUpdate::Event(event)) => {
+                        app.error_message = None;
+                        match event {
+                            AudioEvent::Playing => app.playback_status.playing = true,
+                            AudioEvent::Paused => app.playback_status.playing = false,
+                            AudioEvent::Stopped => {
+                                app.playback_status.playing = false;
+                                app.playback_status.track = None;
+                                app.playback_status.position_ms = 0;
+                                app.playback_status.duration_ms = None;
+                            }
+                            AudioEvent::TrackChanged(track) => {
+                                app.playback_status.duration_ms = track.duration_ms;
+                                app.playback_status.track = Some(track);
+                                app.playback_status.position_ms = 0;
+                            }
+                            AudioEvent::Progress { position_ms } => {
+                                app.playback_status.position_ms = position_ms;
+                            }
+                            AudioEvent::VolumeChanged(volume) => {
+                                app.playback_status.volume = volume;
+                            }
+                            AudioEvent::Snapshot(status) => {
+                                app.playback_status = status;
+                            }
+                        }
+                    }
+                    Some(StatusUpdate::Disconnected(msg)) => {
+                        app.error_message = Some(format!("Connection error: {}", msg));
+                    }
+                    None => {}
+                }
+            }
+            _ = ticker.tick() => {
+                app.update_active_lyric();
+            }
         }
     }
 }
@@ -1,15 +1,19 @@
 use clap::Parser;
-use digitalis::{Library, PlaybackStatus, Track};
-use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use digitalis::{AudioEvent, Library, PlaybackStatus, Track};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
 use std::{
     net::SocketAddr,
     path::PathBuf,
     time::{Duration, Instant},
 };
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info, warn};
 use routes::AudioCommand;
 
+#[cfg(feature = "metrics")]
+mod metrics;
 mod routes;
 
 #[derive(Parser, Debug)]
@@ -20,6 +24,16 @@ struct Args {
     music_dir: PathBuf,
     #[arg(short, long, default_value = "0.0.0.0:3000")]
     bind: SocketAddr,
+    /// Collect and serve Prometheus metrics on GET /metrics. Only available
+    /// when the binary is built with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    #[arg(long)]
+    metrics: bool,
+    /// Push Prometheus metrics to this Pushgateway base URL instead of only
+    /// serving them on GET /metrics. Requires `--metrics`.
+    #[cfg(feature = "metrics")]
+    #[arg(long)]
+    metrics_push_url: Option<String>,
 }
 
 struct AudioThreadState {
@@ -27,13 +41,23 @@ struct AudioThreadState {
     _stream: OutputStream,
     _stream_handle: OutputStreamHandle,
     current_track: Option<Track>,
+    current_path: Option<PathBuf>,
     start_time: Option<Instant>,
     pause_offset: Duration,
     volume: f32,
+    queue: Vec<(PathBuf, Option<Track>)>,
+    queue_index: Option<usize>,
+    device_name: String,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<metrics::Metrics>,
+    event_tx: broadcast::Sender<AudioEvent>,
 }
 
 impl AudioThreadState {
-    fn new() -> anyhow::Result<Self> {
+    fn new(
+        event_tx: broadcast::Sender<AudioEvent>,
+        #[cfg(feature = "metrics")] metrics: Arc<metrics::Metrics>,
+    ) -> anyhow::Result<Self> {
         let (stream, stream_handle) = OutputStream::try_default()?;
         let sink = Sink::try_new(&stream_handle)?;
         sink.set_volume(1.0);
@@ -43,9 +67,16 @@ impl AudioThreadState {
             _stream: stream,
             _stream_handle: stream_handle,
             current_track: None,
+            current_path: None,
             start_time: None,
             pause_offset: Duration::ZERO,
             volume: 1.0,
+            queue: Vec::new(),
+            #[cfg(feature = "metrics")]
+            metrics,
+            queue_index: None,
+            device_name: "default".to_string(),
+            event_tx,
         })
     }
 
@@ -62,29 +93,305 @@ impl AudioThreadState {
         self.sink.as_ref().map(|s| !s.is_paused()).unwrap_or(false)
     }
 
-    fn handle_command(&mut self, cmd: AudioCommand) {
-        match cmd {
-            AudioCommand::Play { path, track } => match std::fs::File::open(&path) {
-                Ok(file) => match Decoder::new(std::io::BufReader::new(file)) {
-                    Ok(source) => {
-                        if let Some(ref sink) = self.sink {
-                            sink.stop();
-                            sink.append(source);
-                            sink.play();
-                            self.current_track = track;
-                            self.start_time = Some(Instant::now());
-                            self.pause_offset = Duration::ZERO;
-                            info!("Started playing: {}", path.display());
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to decode audio: {}", e);
+    fn status(&self) -> PlaybackStatus {
+        PlaybackStatus {
+            playing: self.is_playing(),
+            track: self.current_track.clone(),
+            position_ms: self.position(),
+            duration_ms: self.current_track.as_ref().and_then(|t| t.duration_ms),
+            volume: self.volume,
+            queue: self
+                .queue
+                .iter()
+                .filter_map(|(_, track)| track.clone())
+                .collect(),
+            queue_index: self.queue_index,
+            device: self.device_name.clone(),
+        }
+    }
+
+    /// Push a playback transition to every `/api/events` subscriber. No-op
+    /// (beyond the dropped error) when nobody is listening.
+    fn emit(&self, event: AudioEvent) {
+        let _ = self.event_tx.send(event);
+    }
+
+    /// Decode and play `path` immediately, replacing whatever the sink was
+    /// doing. Used both for direct plays and for queue advancement. Returns
+    /// whether the file was opened and decoded successfully.
+    fn load(&mut self, path: &PathBuf, track: Option<Track>) -> bool {
+        if !self.replace_source(path, track) {
+            return false;
+        }
+        info!("Started playing: {}", path.display());
+        #[cfg(feature = "metrics")]
+        if let Some(ref track) = self.current_track {
+            self.metrics.record_play(&track.artist, &track.album);
+        }
+        #[cfg(feature = "metrics")]
+        self.metrics.set_active(true);
+        if let Some(track) = self.current_track.clone() {
+            self.emit(AudioEvent::TrackChanged(track));
+        }
+        self.emit(AudioEvent::Playing);
+        true
+    }
+
+    /// Decode `path` and replace whatever the sink was doing, without
+    /// recording a play or emitting a playback-transition event. Used by
+    /// `load` for genuine track changes, and by `set_device` to resume the
+    /// same track on a different output without double-counting it.
+    fn replace_source(&mut self, path: &PathBuf, track: Option<Track>) -> bool {
+        match std::fs::File::open(path) {
+            Ok(file) => match Decoder::new(std::io::BufReader::new(file)) {
+                Ok(source) => {
+                    if let Some(ref sink) = self.sink {
+                        sink.stop();
+                        sink.append(source);
+                        sink.play();
+                        self.current_track = track;
+                        self.current_path = Some(path.clone());
+                        self.start_time = Some(Instant::now());
+                        self.pause_offset = Duration::ZERO;
+                        true
+                    } else {
+                        false
                     }
-                },
+                }
                 Err(e) => {
-                    error!("Failed to open file: {}", e);
+                    error!("Failed to decode audio: {}", e);
+                    false
                 }
             },
+            Err(e) => {
+                error!("Failed to open file: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Load the queue entry at `index`, if any, and make it the current
+    /// track. If the entry fails to decode, skip forward instead of
+    /// stalling on an unplayable file.
+    fn play_queue_index(&mut self, index: usize) {
+        let Some((path, track)) = self.queue.get(index).cloned() else {
+            return;
+        };
+        self.queue_index = Some(index);
+        if !self.load(&path, track) {
+            warn!(
+                "Skipping unplayable queue entry at index {}: {}",
+                index,
+                path.display()
+            );
+            self.play_queue_index(index + 1);
+        }
+    }
+
+    /// Seek the current track to `position_ms`. A position past the known
+    /// track duration is rejected with an error. Tries `Sink::try_seek` first; if the decoder doesn't
+    /// support seeking, falls back to reopening the source file and
+    /// skipping to the target position with a fresh `Decoder`.
+    fn seek(&mut self, position_ms: u64) -> Result<(), String> {
+        let duration_ms = self.current_track.as_ref().and_then(|t| t.duration_ms);
+        if let Some(duration_ms) = duration_ms {
+            if position_ms > duration_ms {
+                return Err(format!(
+                    "Seek position {}ms exceeds track duration {}ms",
+                    position_ms, duration_ms
+                ));
+            }
+        }
+
+        let sink = self.sink.as_ref().ok_or("Audio sink unavailable")?;
+        match sink.try_seek(Duration::from_millis(position_ms)) {
+            Ok(()) => {}
+            Err(e) => {
+                warn!("try_seek unsupported ({}), reopening source to skip", e);
+                let path = self
+                    .current_path
+                    .clone()
+                    .ok_or("No track loaded to seek")?;
+                let file = std::fs::File::open(&path).map_err(|e| e.to_string())?;
+                let source =
+                    Decoder::new(std::io::BufReader::new(file)).map_err(|e| e.to_string())?;
+                let skipped = source.skip_duration(Duration::from_millis(position_ms));
+                sink.stop();
+                sink.append(skipped);
+                if !self.is_playing() {
+                    sink.pause();
+                }
+            }
+        }
+
+        self.pause_offset = Duration::from_millis(position_ms);
+        self.start_time = if self.is_playing() {
+            Some(Instant::now())
+        } else {
+            None
+        };
+        info!("Seeked to {}ms", position_ms);
+        #[cfg(feature = "metrics")]
+        self.metrics.record_seek();
+        Ok(())
+    }
+
+    /// Switch audio output to the device named `name`, migrating volume and
+    /// transparently resuming whatever was playing at its current position.
+    /// Never panics on a missing device: falls back to the default output
+    /// and reports the substitution via the returned name.
+    fn set_device(&mut self, name: &str) -> Result<String, String> {
+        use cpal::traits::{DeviceTrait, HostTrait};
+
+        let host = cpal::default_host();
+        let device = host.output_devices().ok().and_then(|mut devices| {
+            devices.find(|d| d.name().map(|n| n == name).unwrap_or(false))
+        });
+
+        let opened = device.and_then(|d| OutputStream::try_from_device(&d).ok());
+        let (stream, stream_handle, resolved_name) = match opened {
+            Some((stream, handle)) => (stream, handle, name.to_string()),
+            None => {
+                warn!(
+                    "Device '{}' unavailable or failed to open; falling back to default",
+                    name
+                );
+                let (stream, handle) = OutputStream::try_default()
+                    .map_err(|e| format!("Failed to open default device: {}", e))?;
+                (stream, handle, "default".to_string())
+            }
+        };
+
+        let sink =
+            Sink::try_new(&stream_handle).map_err(|e| format!("Failed to create sink: {}", e))?;
+        sink.set_volume(self.volume);
+
+        let resume_at = self.position();
+        let was_playing = self.is_playing();
+
+        self._stream = stream;
+        self._stream_handle = stream_handle;
+        self.sink = Some(sink);
+        self.device_name = resolved_name.clone();
+
+        if let Some(path) = self.current_path.clone() {
+            let track = self.current_track.clone();
+            self.replace_source(&path, track);
+            if let Some(ref sink) = self.sink {
+                let _ = sink.try_seek(Duration::from_millis(resume_at));
+                if !was_playing {
+                    sink.pause();
+                }
+            }
+            self.pause_offset = Duration::from_millis(resume_at);
+            self.start_time = if was_playing {
+                Some(Instant::now())
+            } else {
+                None
+            };
+        }
+
+        info!("Switched audio output to '{}'", resolved_name);
+        Ok(resolved_name)
+    }
+
+    /// Called periodically from the audio thread loop. When the sink has
+    /// drained and a track was playing, advance to the next queue entry (the
+    /// entry stays in the queue) so playback continues without an explicit
+    /// `Next` command. The next file is decoded only after the sink has
+    /// drained, so a short gap between tracks remains.
+    fn poll_auto_advance(&mut self) {
+        let sink_drained = self.sink.as_ref().map(|s| s.empty()).unwrap_or(false);
+        if !sink_drained || self.current_track.is_none() {
+            return;
+        }
+        let next_index = self.queue_index.map(|i| i + 1).unwrap_or(0);
+        if next_index < self.queue.len() {
+            info!("Track finished, auto-advancing to next queue entry");
+            self.play_queue_index(next_index);
+        } else {
+            if let Some(ref sink) = self.sink {
+                sink.pause();
+            }
+            self.current_track = None;
+            self.start_time = None;
+            self.pause_offset = Duration::ZERO;
+            #[cfg(feature = "metrics")]
+            self.metrics.set_active(false);
+            self.emit(AudioEvent::Stopped);
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn tick_played_time(&self, elapsed_ms: u64) {
+        if self.is_playing() {
+            self.metrics.add_played_ms(elapsed_ms);
+        }
+    }
+
+    fn handle_command(&mut self, cmd: AudioCommand) {
+        match cmd {
+            AudioCommand::Play { path, track } => {
+                self.queue.clear();
+                self.queue_index = None;
+                self.load(&path, track);
+            }
+            AudioCommand::Enqueue { path, track } => {
+                self.queue.push((path, track));
+                info!("Enqueued track, queue length: {}", self.queue.len());
+                if self.current_track.is_none() && self.queue_index.is_none() {
+                    self.play_queue_index(self.queue.len() - 1);
+                }
+            }
+            AudioCommand::Next => {
+                let next_index = self.queue_index.map(|i| i + 1).unwrap_or(0);
+                if next_index < self.queue.len() {
+                    self.play_queue_index(next_index);
+                } else {
+                    warn!("No next track in queue");
+                }
+            }
+            AudioCommand::Previous => {
+                match self.queue_index {
+                    Some(i) if i > 0 => self.play_queue_index(i - 1),
+                    Some(_) => warn!("Already at the first queue entry"),
+                    None => warn!("No queue history to go back to"),
+                }
+            }
+            AudioCommand::ClearQueue => {
+                self.queue.clear();
+                self.queue_index = None;
+                info!("Queue cleared");
+            }
+            AudioCommand::RemoveQueueIndex(index) => {
+                if index < self.queue.len() {
+                    self.queue.remove(index);
+                    self.queue_index = match self.queue_index {
+                        Some(i) if i == index => None,
+                        Some(i) if i > index => Some(i - 1),
+                        other => other,
+                    };
+                    info!(
+                        "Removed queue entry at index {}, queue length: {}",
+                        index,
+                        self.queue.len()
+                    );
+                } else {
+                    warn!(
+                        "Remove queue index {} out of range (len {})",
+                        index,
+                        self.queue.len()
+                    );
+                }
+            }
+            AudioCommand::GetQueue(tx) => {
+                let tracks = self
+                    .queue
+                    .iter()
+                    .filter_map(|(_, track)| track.clone())
+                    .collect();
+                let _ = tx.send(tracks);
+            }
             AudioCommand::Pause => {
                 if let Some(ref sink) = self.sink {
                     if self.is_playing() {
@@ -94,6 +401,12 @@ impl AudioThreadState {
                         }
                         self.start_time = None;
                         info!("Playback paused");
+                        #[cfg(feature = "metrics")]
+                        {
+                            self.metrics.record_pause();
+                            self.metrics.set_active(false);
+                        }
+                        self.emit(AudioEvent::Paused);
                     }
                 }
             }
@@ -102,6 +415,12 @@ impl AudioThreadState {
                     sink.play();
                     self.start_time = Some(Instant::now());
                     info!("Playback resumed");
+                    #[cfg(feature = "metrics")]
+                    {
+                        self.metrics.record_resume();
+                        self.metrics.set_active(true);
+                    }
+                    self.emit(AudioEvent::Playing);
                 }
             }
             AudioCommand::Stop => {
@@ -110,11 +429,17 @@ impl AudioThreadState {
                     self.current_track = None;
                     self.start_time = None;
                     self.pause_offset = Duration::ZERO;
-                    info!("Playback stopped");
+                    self.queue.clear();
+                    self.queue_index = None;
+                    info!("Playback stopped, queue cleared");
+                    #[cfg(feature = "metrics")]
+                    self.metrics.set_active(false);
+                    self.emit(AudioEvent::Stopped);
                 }
             }
-            AudioCommand::Seek(_position_ms) => {
-                warn!("Seek not yet implemented - requires rodio sink seek support");
+            AudioCommand::Seek(position_ms, tx) => {
+                let result = self.seek(position_ms);
+                let _ = tx.send(result);
             }
             AudioCommand::SetVolume(vol) => {
                 if let Some(ref sink) = self.sink {
@@ -122,27 +447,32 @@ impl AudioThreadState {
                     sink.set_volume(volume);
                     self.volume = volume;
                     info!("Volume set to {}", volume);
+                    self.emit(AudioEvent::VolumeChanged(volume));
                 }
             }
             AudioCommand::GetStatus(tx) => {
-                let status = PlaybackStatus {
-                    playing: self.is_playing(),
-                    track: self.current_track.clone(),
-                    position_ms: self.position(),
-                    duration_ms: None,
-                    volume: self.volume,
-                };
-                let _ = tx.send(status);
+                let _ = tx.send(self.status());
+            }
+            AudioCommand::SetDevice(name, tx) => {
+                let result = self.set_device(&name);
+                let _ = tx.send(result);
             }
         }
     }
 }
 
-fn spawn_audio_thread() -> anyhow::Result<mpsc::Sender<AudioCommand>> {
+fn spawn_audio_thread(
+    event_tx: broadcast::Sender<AudioEvent>,
+    #[cfg(feature = "metrics")] metrics: Arc<metrics::Metrics>,
+) -> anyhow::Result<mpsc::Sender<AudioCommand>> {
     let (tx, mut rx) = mpsc::channel::<AudioCommand>(32);
 
     std::thread::spawn(move || {
-        let mut state = match AudioThreadState::new() {
+        let mut state = match AudioThreadState::new(
+            event_tx,
+            #[cfg(feature = "metrics")]
+            metrics,
+        ) {
             Ok(s) => s,
             Err(e) => {
                 error!("Failed to initialize audio: {}", e);
@@ -150,8 +480,26 @@ fn spawn_audio_thread() -> anyhow::Result<mpsc::Sender<AudioCommand>> {
             }
         };
 
-        while let Some(cmd) = rx.blocking_recv() {
-            state.handle_command(cmd);
+        const POLL_INTERVAL_MS: u64 = 200;
+
+        loop {
+            match rx.try_recv() {
+                Ok(cmd) => {
+                    state.handle_command(cmd);
+                }
+                Err(mpsc::error::TryRecvError::Empty) => {
+                    state.poll_auto_advance();
+                    if state.is_playing() {
+                        state.emit(AudioEvent::Progress {
+                            position_ms: state.position(),
+                        });
+                    }
+                    #[cfg(feature = "metrics")]
+                    state.tick_played_time(POLL_INTERVAL_MS);
+                    std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+                }
+                Err(mpsc::error::TryRecvError::Disconnected) => break,
+            }
         }
     });
 
@@ -238,12 +586,33 @@ async fn main() -> anyhow::Result<()> {
 
     let library = scan_library(&music_root);
 
-    let audio_tx = spawn_audio_thread()?;
+    #[cfg(feature = "metrics")]
+    let metrics = metrics::Metrics::new(args.metrics);
+    #[cfg(feature = "metrics")]
+    metrics.set_tracks_total(library.tracks.len());
+    #[cfg(feature = "metrics")]
+    if let Some(push_url) = args.metrics_push_url.clone() {
+        if args.metrics {
+            metrics::spawn_push_task(metrics.clone(), push_url);
+        } else {
+            warn!("--metrics-push-url given without --metrics; not pushing");
+        }
+    }
+
+    let (event_tx, _) = broadcast::channel::<AudioEvent>(32);
+    let audio_tx = spawn_audio_thread(
+        event_tx.clone(),
+        #[cfg(feature = "metrics")]
+        metrics.clone(),
+    )?;
 
     let state = routes::AppState::new(
         library,
         audio_tx,
         music_root,
+        event_tx,
+        #[cfg(feature = "metrics")]
+        metrics,
     );
 
     let app = routes::setup_router(state);
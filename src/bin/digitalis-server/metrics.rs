@@ -0,0 +1,187 @@
+//! Feature-gated metrics layer: per-artist/album play counts plus atomic
+//! counters for pauses/resumes, seeks, library size, and playback time,
+//! rendered as Prometheus text
+//! format on `GET /metrics` and optionally pushed to a Pushgateway URL.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use tracing::warn;
+
+#[derive(Default)]
+pub struct Metrics {
+    enabled: AtomicBool,
+    plays_by_track: Mutex<HashMap<(String, String), u64>>,
+    pauses_total: AtomicU64,
+    resumes_total: AtomicU64,
+    seeks_total: AtomicU64,
+    tracks_total: AtomicUsize,
+    played_ms_total: AtomicU64,
+    active: AtomicU64,
+}
+
+impl Metrics {
+    /// `enabled` mirrors the `--metrics` flag: when false, every `record_*`/
+    /// `set_*` call is a no-op and `render()` returns only a placeholder
+    /// comment, so building with the `metrics` feature doesn't force
+    /// collection on at runtime.
+    pub fn new(enabled: bool) -> Arc<Self> {
+        Arc::new(Self {
+            enabled: AtomicBool::new(enabled),
+            ..Self::default()
+        })
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn record_play(&self, artist: &str, album: &str) {
+        if !self.enabled() {
+            return;
+        }
+        let mut plays = self.plays_by_track.lock().unwrap();
+        *plays
+            .entry((artist.to_string(), album.to_string()))
+            .or_insert(0) += 1;
+    }
+
+    pub fn record_pause(&self) {
+        if !self.enabled() {
+            return;
+        }
+        self.pauses_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_resume(&self) {
+        if !self.enabled() {
+            return;
+        }
+        self.resumes_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_seek(&self) {
+        if !self.enabled() {
+            return;
+        }
+        self.seeks_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn set_tracks_total(&self, count: usize) {
+        if !self.enabled() {
+            return;
+        }
+        self.tracks_total.store(count, Ordering::Relaxed);
+    }
+
+    pub fn add_played_ms(&self, ms: u64) {
+        if !self.enabled() {
+            return;
+        }
+        self.played_ms_total.fetch_add(ms, Ordering::Relaxed);
+    }
+
+    /// Record whether audio is currently playing, for `digitalis_active`.
+    pub fn set_active(&self, active: bool) {
+        if !self.enabled() {
+            return;
+        }
+        self.active.store(active as u64, Ordering::Relaxed);
+    }
+
+    /// Escape a label value per the Prometheus text exposition format, so a
+    /// tag-derived artist/album containing a backslash, quote, or newline
+    /// can't produce an invalid metric line.
+    fn escape_label(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    }
+
+    /// Render every counter and gauge in Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        if !self.enabled() {
+            return "# metrics collection disabled (pass --metrics to enable)\n".to_string();
+        }
+
+        let mut out = String::new();
+
+        out.push_str("# HELP digitalis_tracks_total Number of tracks in the library\n");
+        out.push_str("# TYPE digitalis_tracks_total gauge\n");
+        out.push_str(&format!(
+            "digitalis_tracks_total {}\n",
+            self.tracks_total.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP digitalis_plays_total Total number of times a track was played, per artist/album\n");
+        out.push_str("# TYPE digitalis_plays_total counter\n");
+        for ((artist, album), count) in self.plays_by_track.lock().unwrap().iter() {
+            out.push_str(&format!(
+                "digitalis_plays_total{{artist=\"{}\",album=\"{}\"}} {}\n",
+                Self::escape_label(artist),
+                Self::escape_label(album),
+                count
+            ));
+        }
+
+        out.push_str("# HELP digitalis_pauses_total Total number of pause commands\n");
+        out.push_str("# TYPE digitalis_pauses_total counter\n");
+        out.push_str(&format!(
+            "digitalis_pauses_total {}\n",
+            self.pauses_total.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP digitalis_resumes_total Total number of resume commands\n");
+        out.push_str("# TYPE digitalis_resumes_total counter\n");
+        out.push_str(&format!(
+            "digitalis_resumes_total {}\n",
+            self.resumes_total.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP digitalis_seeks_total Total number of seek commands\n");
+        out.push_str("# TYPE digitalis_seeks_total counter\n");
+        out.push_str(&format!(
+            "digitalis_seeks_total {}\n",
+            self.seeks_total.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP digitalis_active Whether audio is currently playing\n");
+        out.push_str("# TYPE digitalis_active gauge\n");
+        out.push_str(&format!(
+            "digitalis_active {}\n",
+            self.active.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP digitalis_playback_seconds_total Total seconds of audio played\n");
+        out.push_str("# TYPE digitalis_playback_seconds_total counter\n");
+        out.push_str(&format!(
+            "digitalis_playback_seconds_total {}\n",
+            self.played_ms_total.load(Ordering::Relaxed) as f64 / 1000.0,
+        ));
+
+        out
+    }
+}
+
+/// Push the rendered metrics to a Prometheus Pushgateway every 15 seconds.
+pub fn spawn_push_task(metrics: Arc<Metrics>, push_url: String) {
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        let mut interval = tokio::time::interval(Duration::from_secs(15));
+        loop {
+            interval.tick().await;
+            let body = metrics.render();
+            if let Err(e) = client
+                .post(format!("{}/metrics/job/digitalis", push_url))
+                .body(body)
+                .send()
+                .await
+            {
+                warn!("Failed to push metrics to {}: {}", push_url, e);
+            }
+        }
+    });
+}
@@ -1,14 +1,23 @@
 use axum::{
     Router,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::sse::{Event, KeepAlive, Sse},
     response::Json,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
-use digitalis::{Library, PlayRequest, PlaybackStatus, SeekRequest, Track, VolumeRequest};
+use digitalis::{
+    ApiResponse, AudioEvent, DeviceRequest, Library, PlayRequest, PlaybackStatus, SeekRequest,
+    Track, VolumeRequest,
+};
+use futures_util::StreamExt;
+use lofty::file::TaggedFileExt;
+use serde::Deserialize;
+use std::convert::Infallible;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{RwLock, broadcast, mpsc};
+use tokio_stream::wrappers::BroadcastStream;
 use tower_http::cors::CorsLayer;
 use tracing::{debug, error, info, warn};
 
@@ -17,14 +26,26 @@ pub struct AppState {
     library: Arc<RwLock<Library>>,
     audio_tx: mpsc::Sender<AudioCommand>,
     music_root: PathBuf,
+    event_tx: broadcast::Sender<AudioEvent>,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<crate::metrics::Metrics>,
 }
 
 impl AppState {
-    pub fn new(library: Library, audio_tx: mpsc::Sender<AudioCommand>, music_root: PathBuf) -> Self {
+    pub fn new(
+        library: Library,
+        audio_tx: mpsc::Sender<AudioCommand>,
+        music_root: PathBuf,
+        event_tx: broadcast::Sender<AudioEvent>,
+        #[cfg(feature = "metrics")] metrics: Arc<crate::metrics::Metrics>,
+    ) -> Self {
         AppState {
             library: Arc::new(RwLock::new(library)),
             audio_tx,
             music_root: music_root.clone(),
+            event_tx,
+            #[cfg(feature = "metrics")]
+            metrics,
         }
     }
 }
@@ -35,18 +56,25 @@ pub enum AudioCommand {
     Pause,
     Resume,
     Stop,
-    Seek(u64),
+    Seek(u64, tokio::sync::oneshot::Sender<Result<(), String>>),
     SetVolume(f32),
     GetStatus(tokio::sync::oneshot::Sender<PlaybackStatus>),
+    Enqueue { path: PathBuf, track: Option<Track> },
+    Next,
+    Previous,
+    ClearQueue,
+    RemoveQueueIndex(usize),
+    GetQueue(tokio::sync::oneshot::Sender<Vec<Track>>),
+    SetDevice(String, tokio::sync::oneshot::Sender<Result<String, String>>),
 }
 
-async fn get_library(State(state): State<AppState>) -> Json<Library> {
+async fn get_library(State(state): State<AppState>) -> ApiResponse<Library> {
     debug!("GET /api/library");
     let library = state.library.read().await;
-    Json(library.clone())
+    ApiResponse::Success(library.clone())
 }
 
-async fn get_artists(State(state): State<AppState>) -> Json<Vec<String>> {
+async fn get_artists(State(state): State<AppState>) -> ApiResponse<Vec<String>> {
     debug!("GET /api/library/artists");
     let library = state.library.read().await;
     let mut artists: Vec<String> = library
@@ -57,13 +85,13 @@ async fn get_artists(State(state): State<AppState>) -> Json<Vec<String>> {
         .into_iter()
         .collect();
     artists.sort();
-    Json(artists)
+    ApiResponse::Success(artists)
 }
 
 async fn get_albums(
     Path(artist): Path<String>,
     State(state): State<AppState>,
-) -> Json<Vec<String>> {
+) -> ApiResponse<Vec<String>> {
     debug!("GET /api/library/artists/{}/albums", artist);
     let library = state.library.read().await;
     let mut albums: Vec<String> = library
@@ -75,13 +103,13 @@ async fn get_albums(
         .into_iter()
         .collect();
     albums.sort();
-    Json(albums)
+    ApiResponse::Success(albums)
 }
 
 async fn get_tracks(
     Path((artist, album)): Path<(String, String)>,
     State(state): State<AppState>,
-) -> Json<Vec<Track>> {
+) -> ApiResponse<Vec<Track>> {
     debug!("GET /api/library/artists/{}/{}", artist, album);
     let library = state.library.read().await;
     let mut tracks: Vec<Track> = library
@@ -90,11 +118,110 @@ async fn get_tracks(
         .filter(|t| t.artist == artist && t.album == album)
         .cloned()
         .collect();
-    tracks.sort_by(|a, b| a.title.cmp(&b.title));
-    Json(tracks)
+    tracks.sort_by(|a, b| {
+        a.track_number
+            .unwrap_or(u32::MAX)
+            .cmp(&b.track_number.unwrap_or(u32::MAX))
+            .then(a.title.cmp(&b.title))
+    });
+    ApiResponse::Success(tracks)
+}
+
+#[derive(Debug, Deserialize)]
+struct LyricsQuery {
+    path: String,
 }
 
-async fn play(State(state): State<AppState>, Json(request): Json<PlayRequest>) -> StatusCode {
+/// Look up the `.lrc` lyrics file alongside a track (same path, `.lrc`
+/// extension) and return its raw text, or 404 if there isn't one.
+async fn get_lyrics(
+    State(state): State<AppState>,
+    Query(params): Query<LyricsQuery>,
+) -> Result<String, StatusCode> {
+    debug!("GET /api/lyrics - {}", params.path);
+
+    let relative_path = PathBuf::from(&params.path);
+    let full_path = state.music_root.join(&relative_path);
+    let lrc_path = full_path.with_extension("lrc");
+
+    let canonical = lrc_path.canonicalize().map_err(|_| StatusCode::NOT_FOUND)?;
+    if !canonical.starts_with(&state.music_root) {
+        warn!(
+            "Path traversal attempt detected: {}",
+            canonical.display()
+        );
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    tokio::fs::read_to_string(&canonical)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)
+}
+
+#[derive(Debug, Deserialize)]
+struct CoverQuery {
+    path: String,
+}
+
+/// Extract cover art for a track: embedded tag picture first, falling back
+/// to a `cover`/`folder` image file in the same directory. 404 if neither
+/// exists.
+async fn get_cover(
+    State(state): State<AppState>,
+    Query(params): Query<CoverQuery>,
+) -> Result<([(header::HeaderName, String); 1], Vec<u8>), StatusCode> {
+    debug!("GET /api/cover - {}", params.path);
+
+    let relative_path = PathBuf::from(&params.path);
+    let full_path = state.music_root.join(&relative_path);
+    let canonical = full_path.canonicalize().map_err(|_| StatusCode::NOT_FOUND)?;
+    if !canonical.starts_with(&state.music_root) {
+        warn!("Path traversal attempt detected: {}", canonical.display());
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    if let Ok(tagged_file) = lofty::read_from_path(&canonical) {
+        let picture = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+            .and_then(|tag| tag.pictures().first().cloned());
+        if let Some(picture) = picture {
+            let mime = picture
+                .mime_type()
+                .map(|m| match m {
+                    lofty::picture::MimeType::Png => "image/png",
+                    lofty::picture::MimeType::Jpeg => "image/jpeg",
+                    lofty::picture::MimeType::Tiff => "image/tiff",
+                    lofty::picture::MimeType::Bmp => "image/bmp",
+                    lofty::picture::MimeType::Gif => "image/gif",
+                    _ => "application/octet-stream",
+                })
+                .unwrap_or("image/jpeg")
+                .to_string();
+            return Ok(([(header::CONTENT_TYPE, mime)], picture.data().to_vec()));
+        }
+    }
+
+    if let Some(dir) = canonical.parent() {
+        for name in ["cover.jpg", "cover.jpeg", "cover.png", "folder.jpg", "folder.png"] {
+            if let Ok(bytes) = tokio::fs::read(dir.join(name)).await {
+                let mime = if name.ends_with("png") {
+                    "image/png"
+                } else {
+                    "image/jpeg"
+                };
+                return Ok(([(header::CONTENT_TYPE, mime.to_string())], bytes));
+            }
+        }
+    }
+
+    Err(StatusCode::NOT_FOUND)
+}
+
+async fn play(
+    State(state): State<AppState>,
+    Json(request): Json<PlayRequest>,
+) -> ApiResponse<()> {
     info!("POST /api/play - request.path: {}", request.path);
     debug!("Music root: {}", state.music_root.display());
 
@@ -115,7 +242,10 @@ async fn play(State(state): State<AppState>, Json(request): Json<PlayRequest>) -
                 full_path.display(),
                 e
             );
-            return StatusCode::NOT_FOUND;
+            return ApiResponse::Failure(format!(
+                "Track not found: {}",
+                request.path
+            ));
         }
     };
     debug!("Canonicalized full path: {}", canonical_full_path.display());
@@ -126,7 +256,9 @@ async fn play(State(state): State<AppState>, Json(request): Json<PlayRequest>) -
             "Path traversal attempt detected: {}",
             canonical_full_path.display()
         );
-        return StatusCode::FORBIDDEN;
+        return ApiResponse::Failure(
+            "Path traversal attempt rejected".to_string(),
+        );
     }
 
     // Look up track in library
@@ -151,58 +283,72 @@ async fn play(State(state): State<AppState>, Json(request): Json<PlayRequest>) -
     };
 
     match state.audio_tx.send(cmd).await {
-        Ok(_) => StatusCode::OK,
+        Ok(_) => ApiResponse::Success(()),
         Err(e) => {
             error!("Failed to send play command: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
         }
     }
 }
 
-async fn pause(State(state): State<AppState>) -> StatusCode {
+async fn pause(State(state): State<AppState>) -> ApiResponse<()> {
     info!("POST /api/pause");
     match state.audio_tx.send(AudioCommand::Pause).await {
-        Ok(_) => StatusCode::OK,
+        Ok(_) => ApiResponse::Success(()),
         Err(e) => {
             error!("Failed to send pause command: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
         }
     }
 }
 
-async fn resume(State(state): State<AppState>) -> StatusCode {
+async fn resume(State(state): State<AppState>) -> ApiResponse<()> {
     info!("POST /api/resume");
     match state.audio_tx.send(AudioCommand::Resume).await {
-        Ok(_) => StatusCode::OK,
+        Ok(_) => ApiResponse::Success(()),
         Err(e) => {
             error!("Failed to send resume command: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
         }
     }
 }
 
-async fn stop(State(state): State<AppState>) -> StatusCode {
+async fn stop(State(state): State<AppState>) -> ApiResponse<()> {
     info!("POST /api/stop");
     match state.audio_tx.send(AudioCommand::Stop).await {
-        Ok(_) => StatusCode::OK,
+        Ok(_) => ApiResponse::Success(()),
         Err(e) => {
             error!("Failed to send stop command: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
         }
     }
 }
 
-async fn seek(State(state): State<AppState>, Json(request): Json<SeekRequest>) -> StatusCode {
+async fn seek(
+    State(state): State<AppState>,
+    Json(request): Json<SeekRequest>,
+) -> ApiResponse<()> {
     info!("POST /api/seek - {}ms", request.position_ms);
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
     match state
         .audio_tx
-        .send(AudioCommand::Seek(request.position_ms))
+        .send(AudioCommand::Seek(request.position_ms, tx))
         .await
     {
-        Ok(_) => StatusCode::NOT_IMPLEMENTED,
+        Ok(_) => match rx.await {
+            Ok(Ok(())) => ApiResponse::Success(()),
+            Ok(Err(msg)) => ApiResponse::Failure(msg),
+            Err(e) => {
+                error!("Failed to receive seek result: {}", e);
+                ApiResponse::Fatal(
+                    "Audio thread dropped the seek channel".to_string(),
+                )
+            }
+        },
         Err(e) => {
             error!("Failed to send seek command: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
         }
     }
 }
@@ -210,63 +356,281 @@ async fn seek(State(state): State<AppState>, Json(request): Json<SeekRequest>) -
 async fn set_volume(
     State(state): State<AppState>,
     Json(request): Json<VolumeRequest>,
-) -> StatusCode {
+) -> ApiResponse<()> {
     info!("POST /api/volume - {}", request.volume);
     match state
         .audio_tx
         .send(AudioCommand::SetVolume(request.volume))
         .await
     {
-        Ok(_) => StatusCode::OK,
+        Ok(_) => ApiResponse::Success(()),
         Err(e) => {
             error!("Failed to send volume command: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
         }
     }
 }
 
-async fn get_status(State(state): State<AppState>) -> Json<PlaybackStatus> {
+async fn get_status(State(state): State<AppState>) -> ApiResponse<PlaybackStatus> {
     debug!("GET /api/status");
     let (tx, rx) = tokio::sync::oneshot::channel();
 
     match state.audio_tx.send(AudioCommand::GetStatus(tx)).await {
         Ok(_) => match rx.await {
-            Ok(status) => Json(status),
+            Ok(status) => ApiResponse::Success(status),
             Err(e) => {
                 error!("Failed to receive status: {}", e);
-                Json(PlaybackStatus {
-                    playing: false,
-                    track: None,
-                    position_ms: 0,
-                    duration_ms: None,
-                    volume: 1.0,
-                })
+                ApiResponse::Fatal(
+                    "Audio thread dropped the status channel".to_string(),
+                )
             }
         },
         Err(e) => {
             error!("Failed to send get_status command: {}", e);
-            Json(PlaybackStatus {
-                playing: false,
-                track: None,
-                position_ms: 0,
-                duration_ms: None,
-                volume: 1.0,
-            })
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
         }
     }
 }
 
+async fn enqueue(
+    State(state): State<AppState>,
+    Json(request): Json<PlayRequest>,
+) -> ApiResponse<()> {
+    info!("POST /api/queue - request.path: {}", request.path);
+
+    let relative_path = PathBuf::from(&request.path);
+    let full_path = state.music_root.join(&relative_path);
+
+    let canonical_full_path = match full_path.canonicalize() {
+        Ok(path) => path,
+        Err(e) => {
+            warn!(
+                "Track not found or cannot access: {} - Error: {}",
+                full_path.display(),
+                e
+            );
+            return ApiResponse::Failure(format!(
+                "Track not found: {}",
+                request.path
+            ));
+        }
+    };
+
+    if !canonical_full_path.starts_with(&state.music_root) {
+        warn!(
+            "Path traversal attempt detected: {}",
+            canonical_full_path.display()
+        );
+        return ApiResponse::Failure(
+            "Path traversal attempt rejected".to_string(),
+        );
+    }
+
+    let track = state
+        .library
+        .read()
+        .await
+        .tracks
+        .iter()
+        .find(|t| t.path == request.path)
+        .cloned();
+
+    let cmd = AudioCommand::Enqueue {
+        path: canonical_full_path,
+        track,
+    };
+
+    match state.audio_tx.send(cmd).await {
+        Ok(_) => ApiResponse::Success(()),
+        Err(e) => {
+            error!("Failed to send enqueue command: {}", e);
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
+        }
+    }
+}
+
+async fn next(State(state): State<AppState>) -> ApiResponse<()> {
+    info!("POST /api/next");
+    match state.audio_tx.send(AudioCommand::Next).await {
+        Ok(_) => ApiResponse::Success(()),
+        Err(e) => {
+            error!("Failed to send next command: {}", e);
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
+        }
+    }
+}
+
+async fn previous(State(state): State<AppState>) -> ApiResponse<()> {
+    info!("POST /api/previous");
+    match state.audio_tx.send(AudioCommand::Previous).await {
+        Ok(_) => ApiResponse::Success(()),
+        Err(e) => {
+            error!("Failed to send previous command: {}", e);
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
+        }
+    }
+}
+
+async fn clear_queue(State(state): State<AppState>) -> ApiResponse<()> {
+    info!("DELETE /api/queue");
+    match state.audio_tx.send(AudioCommand::ClearQueue).await {
+        Ok(_) => ApiResponse::Success(()),
+        Err(e) => {
+            error!("Failed to send clear_queue command: {}", e);
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
+        }
+    }
+}
+
+async fn remove_queue_entry(
+    Path(index): Path<usize>,
+    State(state): State<AppState>,
+) -> ApiResponse<()> {
+    info!("DELETE /api/queue/{}", index);
+    match state
+        .audio_tx
+        .send(AudioCommand::RemoveQueueIndex(index))
+        .await
+    {
+        Ok(_) => ApiResponse::Success(()),
+        Err(e) => {
+            error!("Failed to send remove_queue_entry command: {}", e);
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
+        }
+    }
+}
+
+async fn get_queue(State(state): State<AppState>) -> ApiResponse<Vec<Track>> {
+    debug!("GET /api/queue");
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    match state.audio_tx.send(AudioCommand::GetQueue(tx)).await {
+        Ok(_) => match rx.await {
+            Ok(queue) => ApiResponse::Success(queue),
+            Err(e) => {
+                error!("Failed to receive queue: {}", e);
+                ApiResponse::Fatal(
+                    "Audio thread dropped the queue channel".to_string(),
+                )
+            }
+        },
+        Err(e) => {
+            error!("Failed to send get_queue command: {}", e);
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
+        }
+    }
+}
+
+async fn get_devices() -> ApiResponse<Vec<String>> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    debug!("GET /api/devices");
+    let host = cpal::default_host();
+    match host.output_devices() {
+        Ok(devices) => {
+            let names = devices.filter_map(|d| d.name().ok()).collect();
+            ApiResponse::Success(names)
+        }
+        Err(e) => {
+            error!("Failed to enumerate output devices: {}", e);
+            ApiResponse::Failure("Failed to enumerate output devices".to_string())
+        }
+    }
+}
+
+async fn set_device(
+    State(state): State<AppState>,
+    Json(request): Json<DeviceRequest>,
+) -> ApiResponse<String> {
+    info!("POST /api/device - {}", request.name);
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    match state
+        .audio_tx
+        .send(AudioCommand::SetDevice(request.name, tx))
+        .await
+    {
+        Ok(_) => match rx.await {
+            Ok(Ok(name)) => ApiResponse::Success(name),
+            Ok(Err(msg)) => ApiResponse::Failure(msg),
+            Err(e) => {
+                error!("Failed to receive device switch result: {}", e);
+                ApiResponse::Fatal("Audio thread dropped the device channel".to_string())
+            }
+        },
+        Err(e) => {
+            error!("Failed to send set_device command: {}", e);
+            ApiResponse::Fatal("Audio thread is unreachable".to_string())
+        }
+    }
+}
+
+async fn events(
+    State(state): State<AppState>,
+) -> Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>> {
+    debug!("GET /api/events");
+
+    // Subscribe before fetching the snapshot so a transition that happens
+    // in between is still observed (as a duplicate, which the client
+    // handles fine) rather than lost.
+    let rx = state.event_tx.subscribe();
+
+    let (tx, status_rx) = tokio::sync::oneshot::channel();
+    let snapshot = match state.audio_tx.send(AudioCommand::GetStatus(tx)).await {
+        Ok(_) => status_rx.await.ok().map(AudioEvent::Snapshot),
+        Err(e) => {
+            error!("Failed to request status snapshot for new SSE subscriber: {}", e);
+            None
+        }
+    };
+
+    let snapshot_stream = futures_util::stream::iter(snapshot).filter_map(|event| async move {
+        match serde_json::to_string(&event) {
+            Ok(data) => Some(Ok(Event::default().data(data))),
+            Err(e) => {
+                error!("Failed to serialize snapshot for SSE: {}", e);
+                None
+            }
+        }
+    });
+    let event_stream = BroadcastStream::new(rx).filter_map(|msg| async move {
+        match msg {
+            Ok(event) => match serde_json::to_string(&event) {
+                Ok(data) => Some(Ok(Event::default().data(data))),
+                Err(e) => {
+                    error!("Failed to serialize event for SSE: {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                warn!("SSE subscriber lagged: {}", e);
+                None
+            }
+        }
+    });
+    let stream = snapshot_stream.chain(event_stream);
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
 async fn health_check() -> &'static str {
     "OK"
 }
 
+#[cfg(feature = "metrics")]
+async fn get_metrics(State(state): State<AppState>) -> String {
+    debug!("GET /metrics");
+    state.metrics.render()
+}
+
 pub fn setup_router(state: AppState) -> Router {
-    Router::new()
+    let router = Router::new()
         .route("/health", get(health_check))
         .route("/api/library", get(get_library))
         .route("/api/library/artists", get(get_artists))
         .route("/api/library/artists/{artist}/albums", get(get_albums))
         .route("/api/library/artists/{artist}/{album}", get(get_tracks))
+        .route("/api/lyrics", get(get_lyrics))
+        .route("/api/cover", get(get_cover))
         .route("/api/play", post(play))
         .route("/api/pause", post(pause))
         .route("/api/resume", post(resume))
@@ -274,6 +638,17 @@ pub fn setup_router(state: AppState) -> Router {
         .route("/api/seek", post(seek))
         .route("/api/volume", post(set_volume))
         .route("/api/status", get(get_status))
-        .layer(CorsLayer::permissive())
-        .with_state(state)
+        .route("/api/queue", post(enqueue).get(get_queue).delete(clear_queue))
+        .route("/api/queue/{index}", delete(remove_queue_entry))
+        .route("/api/next", post(next))
+        .route("/api/previous", post(previous))
+        .route("/api/events", get(events))
+        .route("/api/devices", get(get_devices))
+        .route("/api/device", post(set_device))
+        .layer(CorsLayer::permissive());
+
+    #[cfg(feature = "metrics")]
+    let router = router.route("/metrics", get(get_metrics));
+
+    router.with_state(state)
 }
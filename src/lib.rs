@@ -1,3 +1,8 @@
+use axum::Json;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lofty::file::{AudioFile, TaggedFileExt};
+use lofty::prelude::Accessor;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -5,6 +10,10 @@ use std::path::PathBuf;
 pub struct Track {
     pub path: String,
     pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub track_number: Option<u32>,
+    pub duration_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +23,9 @@ pub struct PlaybackStatus {
     pub position_ms: u64,
     pub duration_ms: Option<u64>,
     pub volume: f32,
+    pub queue: Vec<Track>,
+    pub queue_index: Option<usize>,
+    pub device: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +48,58 @@ pub struct VolumeRequest {
     pub volume: f32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceRequest {
+    pub name: String,
+}
+
+/// Tagged outcome envelope for HTTP API responses, so clients can tell a
+/// recoverable failure (bad input, missing track) apart from a fatal one
+/// (the audio thread or its channel broke) instead of inferring it from a
+/// bare status code. Shared between client and server so the client can
+/// deserialize exactly what the server serializes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "content")]
+pub enum ApiResponse<T> {
+    Success(T),
+    Failure(String),
+    Fatal(String),
+}
+
+impl<T: Serialize> IntoResponse for ApiResponse<T> {
+    /// Carries the same discriminated JSON body on every path while still
+    /// setting an HTTP status a client can branch on without parsing: 200
+    /// for `Success`, 400 for a recoverable `Failure`, 500 for a `Fatal`
+    /// audio-thread/channel breakage.
+    fn into_response(self) -> Response {
+        let status = match &self {
+            ApiResponse::Success(_) => StatusCode::OK,
+            ApiResponse::Failure(_) => StatusCode::BAD_REQUEST,
+            ApiResponse::Fatal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(self)).into_response()
+    }
+}
+
+/// Playback state transitions pushed to `/api/events` subscribers, so
+/// clients can react to what changed instead of diffing successive
+/// `PlaybackStatus` snapshots. Shared between client and server for the
+/// same reason as `ApiResponse`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "content")]
+pub enum AudioEvent {
+    Playing,
+    Paused,
+    Stopped,
+    TrackChanged(Track),
+    Progress { position_ms: u64 },
+    VolumeChanged(f32),
+    /// Full state sent to a client as soon as it subscribes to
+    /// `/api/events`, so one that connects (or reconnects) mid-playback
+    /// doesn't sit stale until the next real transition.
+    Snapshot(PlaybackStatus),
+}
+
 impl Track {
     pub fn from_path(full_path: &PathBuf, music_root: &PathBuf) -> Option<Self> {
         // Normalize paths to ensure consistent handling
@@ -62,15 +126,49 @@ impl Track {
         }
 
         let filename = components[components.len() - 1].to_str()?;
-        let title = filename
+        let filename_title = filename
             .rsplit_once('.')
             .map(|(name, _)| name)
             .unwrap_or(filename)
             .to_string();
+        let dir_artist = components[components.len() - 3].to_str()?.to_string();
+        let dir_album = components[components.len() - 2].to_str()?.to_string();
+
+        // Prefer embedded ID3/Vorbis/MP4 tags; fall back to the directory
+        // layout (music_root/Artist/Album/file) when tags are missing or the
+        // file can't be probed at all.
+        let tagged_file = lofty::read_from_path(&full_path).ok();
+        let tag = tagged_file
+            .as_ref()
+            .and_then(|f| f.primary_tag().or_else(|| f.first_tag()));
+
+        let title = tag
+            .and_then(|t| t.title())
+            .map(|s| s.to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or(filename_title);
+        let artist = tag
+            .and_then(|t| t.artist())
+            .map(|s| s.to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or(dir_artist);
+        let album = tag
+            .and_then(|t| t.album())
+            .map(|s| s.to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or(dir_album);
+        let track_number = tag.and_then(|t| t.track());
+        let duration_ms = tagged_file
+            .as_ref()
+            .map(|f| f.properties().duration().as_millis() as u64);
 
         Some(Track {
             path: path_str,
             title,
+            artist,
+            album,
+            track_number,
+            duration_ms,
         })
     }
 }
@@ -83,6 +181,9 @@ impl Default for PlaybackStatus {
             position_ms: 0,
             duration_ms: None,
             volume: 1.0,
+            queue: Vec::new(),
+            queue_index: None,
+            device: "default".to_string(),
         }
     }
 }